@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+
+use crate::{EntityId, Quaternion, Rotation, TransformComponent, Vector3};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interpolation {
+    Step,
+    Linear,
+}
+
+/// The sampled values for one animated `TransformComponent` property,
+/// one entry per keyframe in the owning `Track`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Keyframes {
+    Translation(Vec<Vector3>),
+    Scale(Vec<Vector3>),
+    Rotation(Vec<Quaternion>),
+}
+
+impl Keyframes {
+    fn len(&self) -> usize {
+        match self {
+            Keyframes::Translation(values) => values.len(),
+            Keyframes::Scale(values) => values.len(),
+            Keyframes::Rotation(values) => values.len(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Track {
+    pub target: EntityId,
+    pub times: Vec<f32>,
+    pub keyframes: Keyframes,
+    pub interpolation: Interpolation,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnimationClip {
+    pub name: String,
+    pub duration: f32,
+    pub tracks: Vec<Track>,
+}
+
+impl AnimationClip {
+    /// Evaluates every track at `time`, returning one `TransformComponent`
+    /// per animated entity. Tracks that don't cover all three properties
+    /// leave the untouched properties at their identity value; times
+    /// before the first or after the last keyframe clamp to the endpoint.
+    pub fn sample(&self, time: f32) -> BTreeMap<EntityId, TransformComponent> {
+        let mut result: BTreeMap<EntityId, TransformComponent> = BTreeMap::new();
+
+        for track in &self.tracks {
+            if track.times.is_empty() || track.times.len() != track.keyframes.len() {
+                continue;
+            }
+
+            let transform = result
+                .entry(track.target)
+                .or_insert_with(TransformComponent::identity);
+            let (lower, upper, factor) = locate(&track.times, time);
+            let factor = match track.interpolation {
+                Interpolation::Step => 0.0,
+                Interpolation::Linear => factor,
+            };
+
+            match &track.keyframes {
+                Keyframes::Translation(values) => {
+                    transform.position = lerp_vector3(values[lower], values[upper], factor);
+                }
+                Keyframes::Scale(values) => {
+                    transform.scale = lerp_vector3(values[lower], values[upper], factor);
+                }
+                Keyframes::Rotation(values) => {
+                    transform.rotation =
+                        Rotation::Quaternion(Quaternion::slerp(values[lower], values[upper], factor));
+                }
+            }
+        }
+
+        result
+    }
+}
+
+fn lerp_vector3(a: Vector3, b: Vector3, t: f64) -> Vector3 {
+    a.add(b.sub(a).scale(t))
+}
+
+/// Binary-searches `times` for the keyframe pair bracketing `time`,
+/// returning `(lower, upper, factor)` where `factor` is the normalized
+/// position between them. Clamps to the first/last keyframe outside the
+/// track's range. `times` must be non-empty; callers guard this.
+fn locate(times: &[f32], time: f32) -> (usize, usize, f64) {
+    let last = times.len() - 1;
+    if time <= times[0] {
+        return (0, 0, 0.0);
+    }
+    if time >= times[last] {
+        return (last, last, 0.0);
+    }
+
+    let upper = times.partition_point(|&t| t <= time);
+    let lower = upper - 1;
+    let span = times[upper] - times[lower];
+    let factor = if span > 0.0 {
+        ((time - times[lower]) / span) as f64
+    } else {
+        0.0
+    };
+    (lower, upper, factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translation_clip() -> AnimationClip {
+        AnimationClip {
+            name: "slide".to_string(),
+            duration: 2.0,
+            tracks: vec![Track {
+                target: 1,
+                times: vec![0.0, 1.0, 2.0],
+                keyframes: Keyframes::Translation(vec![
+                    Vector3::zero(),
+                    Vector3::new(10.0, 0.0, 0.0),
+                    Vector3::new(10.0, 10.0, 0.0),
+                ]),
+                interpolation: Interpolation::Linear,
+            }],
+        }
+    }
+
+    #[test]
+    fn sample_interpolates_linearly_between_keyframes() {
+        let clip = translation_clip();
+        let transforms = clip.sample(0.5);
+        let transform = transforms.get(&1).unwrap();
+        assert_eq!(transform.position, Vector3::new(5.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn sample_clamps_before_first_and_after_last_keyframe() {
+        let clip = translation_clip();
+        assert_eq!(
+            clip.sample(-1.0).get(&1).unwrap().position,
+            Vector3::zero()
+        );
+        assert_eq!(
+            clip.sample(5.0).get(&1).unwrap().position,
+            Vector3::new(10.0, 10.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn step_interpolation_holds_lower_keyframe() {
+        let mut clip = translation_clip();
+        clip.tracks[0].interpolation = Interpolation::Step;
+        let transform = *clip.sample(0.9).get(&1).unwrap();
+        assert_eq!(transform.position, Vector3::zero());
+    }
+
+    #[test]
+    fn rotation_track_slerps_between_keyframes() {
+        let clip = AnimationClip {
+            name: "spin".to_string(),
+            duration: 1.0,
+            tracks: vec![Track {
+                target: 1,
+                times: vec![0.0, 1.0],
+                keyframes: Keyframes::Rotation(vec![
+                    Quaternion::identity(),
+                    Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), std::f64::consts::FRAC_PI_2),
+                ]),
+                interpolation: Interpolation::Linear,
+            }],
+        };
+
+        let transform = *clip.sample(0.5).get(&1).unwrap();
+        let expected = Quaternion::slerp(
+            Quaternion::identity(),
+            Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), std::f64::consts::FRAC_PI_2),
+            0.5,
+        );
+        assert_eq!(transform.rotation, Rotation::Quaternion(expected));
+    }
+
+    #[test]
+    fn sample_skips_empty_or_mismatched_tracks_instead_of_panicking() {
+        let clip = AnimationClip {
+            name: "broken".to_string(),
+            duration: 1.0,
+            tracks: vec![
+                Track {
+                    target: 1,
+                    times: vec![],
+                    keyframes: Keyframes::Translation(vec![]),
+                    interpolation: Interpolation::Linear,
+                },
+                Track {
+                    target: 2,
+                    times: vec![0.0, 1.0],
+                    keyframes: Keyframes::Translation(vec![Vector3::zero()]),
+                    interpolation: Interpolation::Linear,
+                },
+            ],
+        };
+
+        let transforms = clip.sample(0.5);
+        assert!(transforms.get(&1).is_none());
+        assert!(transforms.get(&2).is_none());
+    }
+}
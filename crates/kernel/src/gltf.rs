@@ -0,0 +1,995 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    Aabb, AssetId, AssetRegistry, BufferLayout, BufferLayoutEntry, ComponentId, ComponentRefs,
+    ComponentTable, EntityId, EntityRecord, GeometryComponent, GeometryTopology, IndexFormat,
+    MaterialAsset, MaterialComponent, Mesh, MeshAsset, Quaternion, Rotation, SceneGraph,
+    SceneMetadata, SchemaVersion, TextureAsset, TransformComponent, Unit, Vector3,
+};
+
+/// The raw mesh vertex/index data a `SceneGraph` references by `AssetId`
+/// but does not itself store (see `AssetRegistry`/`MeshAsset`).
+pub type MeshData = BTreeMap<AssetId, Mesh>;
+
+#[derive(Debug)]
+pub enum GltfError {
+    Json(String),
+    Missing(&'static str),
+    Unsupported(String),
+}
+
+/// The JSON chunk plus the single binary buffer produced by `export`.
+pub struct GltfDocument {
+    pub json: String,
+    pub buffer: Vec<u8>,
+}
+
+fn unit_to_meters(unit: Unit) -> f64 {
+    match unit {
+        Unit::Mm => 0.001,
+        Unit::Cm => 0.01,
+        Unit::M => 1.0,
+    }
+}
+
+fn meters_to_unit(scale: f64) -> Unit {
+    if (scale - 0.001).abs() < 1e-6 {
+        Unit::Mm
+    } else if (scale - 0.01).abs() < 1e-6 {
+        Unit::Cm
+    } else {
+        Unit::M
+    }
+}
+
+/// Maps a `SceneGraph` to glTF 2.0: entities become nodes with TRS,
+/// `MeshAsset` buffer layouts become accessors/bufferViews into a single
+/// packed binary buffer, `MaterialComponent` becomes `pbrMetallicRoughness`,
+/// and `SceneMetadata.unit` becomes a root node scale.
+pub fn export(scene: &SceneGraph, meshes: &MeshData) -> GltfDocument {
+    let mut buffer = Vec::new();
+    let mut mesh_json_by_id: BTreeMap<AssetId, usize> = BTreeMap::new();
+    let mut meshes_json = Vec::new();
+    let mut accessors_json = Vec::new();
+    let mut buffer_views_json = Vec::new();
+
+    let material_index_by_component: BTreeMap<ComponentId, usize> = scene
+        .components
+        .materials
+        .keys()
+        .enumerate()
+        .map(|(index, &component_id)| (component_id, index))
+        .collect();
+
+    // glTF attaches a material to a primitive (i.e. to the mesh), while our
+    // component model attaches it to the entity. Use the first entity that
+    // references a given mesh asset and carries a material as that mesh's
+    // primitive material.
+    let mut material_for_mesh: BTreeMap<AssetId, ComponentId> = BTreeMap::new();
+    for entity in &scene.entities {
+        let (Some(geometry_id), Some(material_id)) =
+            (entity.components.geometry, entity.components.material)
+        else {
+            continue;
+        };
+        let Some(geometry) = scene.components.geometries.get(&geometry_id) else {
+            continue;
+        };
+        material_for_mesh.entry(geometry.mesh).or_insert(material_id);
+    }
+
+    for (asset_id, asset) in &scene.assets.meshes {
+        let Some(mesh) = meshes.get(asset_id) else {
+            continue;
+        };
+
+        // Honor the asset's declared layout rather than always packing
+        // positions tightly: pad `offset` bytes before each vertex's
+        // position and `stride` bytes total per vertex record, so the
+        // buffer actually matches the `byteStride` the bufferView declares.
+        let position_offset_in_record = asset.layout.position.offset as usize;
+        let position_stride = asset.layout.position.stride as usize;
+
+        let position_view = buffer_views_json.len();
+        let position_offset = buffer.len();
+        for chunk in mesh.positions.chunks_exact(3) {
+            buffer.resize(buffer.len() + position_offset_in_record, 0);
+            for &component in chunk {
+                buffer.extend_from_slice(&component.to_le_bytes());
+            }
+            let written = position_offset_in_record + 12;
+            buffer.resize(buffer.len() + position_stride.saturating_sub(written), 0);
+        }
+        let position_len = buffer.len() - position_offset;
+        buffer_views_json.push(json_object([
+            ("buffer", Json::Number(0.0)),
+            ("byteOffset", Json::Number(position_offset as f64)),
+            ("byteLength", Json::Number(position_len as f64)),
+            ("byteStride", Json::Number(position_stride as f64)),
+            ("target", Json::Number(34962.0)),
+        ]));
+
+        let (bounds_min, bounds_max) = mesh_bounds(&mesh.positions);
+        let position_accessor = accessors_json.len();
+        accessors_json.push(json_object([
+            ("bufferView", Json::Number(position_view as f64)),
+            ("byteOffset", Json::Number(position_offset_in_record as f64)),
+            ("componentType", Json::Number(5126.0)),
+            ("count", Json::Number((mesh.positions.len() / 3) as f64)),
+            ("type", Json::String("VEC3".to_string())),
+            ("min", Json::Array(vec3_json(bounds_min))),
+            ("max", Json::Array(vec3_json(bounds_max))),
+        ]));
+
+        let (index_component_type, index_component_size) = match asset.index_format {
+            IndexFormat::Uint16 => (5123.0, 2usize),
+            IndexFormat::Uint32 => (5125.0, 4usize),
+        };
+        let index_view = buffer_views_json.len();
+        let index_offset = buffer.len();
+        for &index in &mesh.indices {
+            match asset.index_format {
+                IndexFormat::Uint16 => buffer.extend_from_slice(&(index as u16).to_le_bytes()),
+                IndexFormat::Uint32 => buffer.extend_from_slice(&index.to_le_bytes()),
+            }
+        }
+        let index_len = buffer.len() - index_offset;
+        buffer_views_json.push(json_object([
+            ("buffer", Json::Number(0.0)),
+            ("byteOffset", Json::Number(index_offset as f64)),
+            ("byteLength", Json::Number(index_len as f64)),
+            ("target", Json::Number(34963.0)),
+        ]));
+
+        let index_accessor = accessors_json.len();
+        accessors_json.push(json_object([
+            ("bufferView", Json::Number(index_view as f64)),
+            ("componentType", Json::Number(index_component_type)),
+            ("count", Json::Number((index_len / index_component_size) as f64)),
+            ("type", Json::String("SCALAR".to_string())),
+        ]));
+
+        let mut primitive_fields = vec![
+            (
+                "attributes",
+                json_object([("POSITION", Json::Number(position_accessor as f64))]),
+            ),
+            ("indices", Json::Number(index_accessor as f64)),
+            (
+                "mode",
+                Json::Number(match asset.topology {
+                    GeometryTopology::Triangles => 4.0,
+                    GeometryTopology::Lines => 1.0,
+                }),
+            ),
+        ];
+        if let Some(material_index) = material_for_mesh
+            .get(asset_id)
+            .and_then(|component_id| material_index_by_component.get(component_id))
+        {
+            primitive_fields.push(("material", Json::Number(*material_index as f64)));
+        }
+
+        mesh_json_by_id.insert(*asset_id, meshes_json.len());
+        meshes_json.push(json_object([(
+            "primitives",
+            Json::Array(vec![json_object_vec(primitive_fields)]),
+        )]));
+    }
+
+    let materials_json: Vec<Json> = scene
+        .components
+        .materials
+        .values()
+        .map(|material| {
+            let alpha_mode = if material.opacity < 1.0 { "BLEND" } else { "OPAQUE" };
+            json_object([
+                (
+                    "pbrMetallicRoughness",
+                    json_object([
+                        (
+                            "baseColorFactor",
+                            Json::Array(vec![
+                                Json::Number(material.base_color[0] as f64),
+                                Json::Number(material.base_color[1] as f64),
+                                Json::Number(material.base_color[2] as f64),
+                                Json::Number(material.opacity as f64),
+                            ]),
+                        ),
+                        ("metallicFactor", Json::Number(material.metallic as f64)),
+                        ("roughnessFactor", Json::Number(material.roughness as f64)),
+                    ]),
+                ),
+                ("alphaMode", Json::String(alpha_mode.to_string())),
+            ])
+        })
+        .collect();
+
+    let textures_json: Vec<Json> = scene
+        .assets
+        .textures
+        .values()
+        .map(|texture| json_object([("uri", Json::String(texture.uri.clone()))]))
+        .collect();
+
+    let mut nodes_json: Vec<Json> = Vec::with_capacity(scene.entities.len() + 1);
+    for entity in &scene.entities {
+        let transform = entity
+            .components
+            .transform
+            .and_then(|id| scene.components.transforms.get(&id))
+            .copied()
+            .unwrap_or_else(TransformComponent::identity);
+        let quaternion = transform.rotation.to_quaternion();
+
+        let mut fields = vec![
+            ("translation", Json::Array(vec3_json(transform.position))),
+            (
+                "rotation",
+                Json::Array(vec![
+                    Json::Number(quaternion.x),
+                    Json::Number(quaternion.y),
+                    Json::Number(quaternion.z),
+                    Json::Number(quaternion.w),
+                ]),
+            ),
+            ("scale", Json::Array(vec3_json(transform.scale))),
+        ];
+        if let Some(name) = &entity.name {
+            fields.push(("name", Json::String(name.clone())));
+        }
+        if let Some(geometry_id) = entity.components.geometry {
+            if let Some(geometry) = scene.components.geometries.get(&geometry_id) {
+                if let Some(&mesh_index) = mesh_json_by_id.get(&geometry.mesh) {
+                    fields.push(("mesh", Json::Number(mesh_index as f64)));
+                }
+            }
+        }
+
+        nodes_json.push(json_object_vec(fields));
+    }
+
+    let root_scale = unit_to_meters(scene.metadata.unit);
+    nodes_json.insert(
+        0,
+        json_object([
+            ("name", Json::String(scene.metadata.name.clone())),
+            ("scale", Json::Array(vec3_json(Vector3::new(root_scale, root_scale, root_scale)))),
+            (
+                "children",
+                Json::Array((0..scene.entities.len()).map(|i| Json::Number((i + 1) as f64)).collect()),
+            ),
+        ]),
+    );
+
+    let document = json_object([
+        (
+            "asset",
+            json_object([("version", Json::String("2.0".to_string()))]),
+        ),
+        ("scene", Json::Number(0.0)),
+        (
+            "scenes",
+            Json::Array(vec![json_object([(
+                "nodes",
+                Json::Array(vec![Json::Number(0.0)]),
+            )])]),
+        ),
+        ("nodes", Json::Array(nodes_json)),
+        ("meshes", Json::Array(meshes_json)),
+        ("accessors", Json::Array(accessors_json)),
+        ("bufferViews", Json::Array(buffer_views_json)),
+        (
+            "buffers",
+            Json::Array(vec![json_object([("byteLength", Json::Number(buffer.len() as f64))])]),
+        ),
+        ("materials", Json::Array(materials_json)),
+        ("textures", Json::Array(textures_json)),
+    ]);
+
+    GltfDocument {
+        json: document.to_string(),
+        buffer,
+    }
+}
+
+fn mesh_bounds(positions: &[f32]) -> (Vector3, Vector3) {
+    let aabb = positions.chunks_exact(3).fold(Aabb::empty(), |acc, p| {
+        acc.union_point(Vector3::new(p[0] as f64, p[1] as f64, p[2] as f64))
+    });
+    (aabb.min, aabb.max)
+}
+
+fn vec3_json(v: Vector3) -> Vec<Json> {
+    vec![Json::Number(v.x), Json::Number(v.y), Json::Number(v.z)]
+}
+
+/// Rebuilds a `SceneGraph` and its backing mesh data from a glTF JSON
+/// chunk and binary buffer produced by `export` (or any glTF 2.0 asset
+/// using a single buffer and the subset of features `export` writes).
+pub fn import(json: &str, buffer: &[u8]) -> Result<(SceneGraph, MeshData), GltfError> {
+    let document = Json::parse(json).map_err(GltfError::Json)?;
+
+    let nodes = document.get("nodes").and_then(Json::as_array).unwrap_or(&[]);
+    let gltf_meshes = document.get("meshes").and_then(Json::as_array).unwrap_or(&[]);
+    let accessors = document.get("accessors").and_then(Json::as_array).unwrap_or(&[]);
+    let buffer_views = document.get("bufferViews").and_then(Json::as_array).unwrap_or(&[]);
+    let materials = document.get("materials").and_then(Json::as_array).unwrap_or(&[]);
+    let textures = document.get("textures").and_then(Json::as_array).unwrap_or(&[]);
+
+    // Reads a VEC3 f32 accessor, honoring an interleaved `byteStride` on the
+    // bufferView and a per-attribute `byteOffset` on the accessor, rather
+    // than assuming the view is a tightly packed array of vec3s.
+    let read_accessor_f32 = |accessor_index: usize| -> Result<Vec<f32>, GltfError> {
+        const ELEMENT_SIZE: usize = 3 * 4;
+
+        let accessor = accessors
+            .get(accessor_index)
+            .ok_or(GltfError::Missing("accessor"))?;
+        let view_index = accessor
+            .get("bufferView")
+            .and_then(Json::as_u64)
+            .ok_or(GltfError::Missing("bufferView"))? as usize;
+        let view = buffer_views.get(view_index).ok_or(GltfError::Missing("bufferView"))?;
+        let view_offset = view.get("byteOffset").and_then(Json::as_u64).unwrap_or(0) as usize;
+        let view_length = view.get("byteLength").and_then(Json::as_u64).unwrap_or(0) as usize;
+        let accessor_offset = accessor.get("byteOffset").and_then(Json::as_u64).unwrap_or(0) as usize;
+        let stride = view
+            .get("byteStride")
+            .and_then(Json::as_u64)
+            .map(|s| s as usize)
+            .unwrap_or(ELEMENT_SIZE);
+        let count = accessor.get("count").and_then(Json::as_u64).unwrap_or(0) as usize;
+
+        let slice = buffer
+            .get(view_offset..view_offset + view_length)
+            .ok_or(GltfError::Unsupported("buffer view out of range".to_string()))?;
+
+        let mut values = Vec::with_capacity(count * 3);
+        for i in 0..count {
+            let start = accessor_offset + i * stride;
+            let record = slice
+                .get(start..start + ELEMENT_SIZE)
+                .ok_or(GltfError::Unsupported("accessor out of range".to_string()))?;
+            values.extend(
+                record
+                    .chunks_exact(4)
+                    .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+            );
+        }
+        Ok(values)
+    };
+
+    let read_accessor_u32 = |accessor_index: usize| -> Result<Vec<u32>, GltfError> {
+        let accessor = accessors
+            .get(accessor_index)
+            .ok_or(GltfError::Missing("accessor"))?;
+        let view_index = accessor
+            .get("bufferView")
+            .and_then(Json::as_u64)
+            .ok_or(GltfError::Missing("bufferView"))? as usize;
+        let view = buffer_views.get(view_index).ok_or(GltfError::Missing("bufferView"))?;
+        let offset = view.get("byteOffset").and_then(Json::as_u64).unwrap_or(0) as usize;
+        let length = view.get("byteLength").and_then(Json::as_u64).unwrap_or(0) as usize;
+        let slice = buffer
+            .get(offset..offset + length)
+            .ok_or(GltfError::Unsupported("buffer view out of range".to_string()))?;
+        let component_type = accessor.get("componentType").and_then(Json::as_u64).unwrap_or(5125);
+        Ok(if component_type == 5123 {
+            slice
+                .chunks_exact(2)
+                .map(|b| u16::from_le_bytes([b[0], b[1]]) as u32)
+                .collect()
+        } else {
+            slice
+                .chunks_exact(4)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect()
+        })
+    };
+
+    let mut meshes = MeshData::new();
+    let mut mesh_assets = BTreeMap::new();
+    let mut material_by_mesh_asset: BTreeMap<AssetId, ComponentId> = BTreeMap::new();
+    for (index, mesh) in gltf_meshes.iter().enumerate() {
+        let primitive = mesh
+            .get("primitives")
+            .and_then(Json::as_array)
+            .and_then(|p| p.first())
+            .ok_or(GltfError::Missing("primitives"))?;
+        let position_accessor = primitive
+            .get("attributes")
+            .and_then(|a| a.get("POSITION"))
+            .and_then(Json::as_u64)
+            .ok_or(GltfError::Missing("POSITION"))? as usize;
+        let index_accessor = primitive
+            .get("indices")
+            .and_then(Json::as_u64)
+            .ok_or(GltfError::Missing("indices"))? as usize;
+
+        let positions = read_accessor_f32(position_accessor)?;
+        let indices = read_accessor_u32(index_accessor)?;
+        let asset_id = index as AssetId;
+
+        if let Some(material_index) = primitive.get("material").and_then(Json::as_u64) {
+            material_by_mesh_asset.insert(asset_id, material_index as ComponentId);
+        }
+
+        let index_accessor_json = accessors.get(index_accessor);
+        let index_format = match index_accessor_json
+            .and_then(|a| a.get("componentType"))
+            .and_then(Json::as_u64)
+        {
+            Some(5123) => IndexFormat::Uint16,
+            _ => IndexFormat::Uint32,
+        };
+        let topology = match primitive.get("mode").and_then(Json::as_u64) {
+            Some(1) => GeometryTopology::Lines,
+            _ => GeometryTopology::Triangles,
+        };
+        let stride = buffer_views
+            .get(
+                accessors
+                    .get(position_accessor)
+                    .and_then(|a| a.get("bufferView"))
+                    .and_then(Json::as_u64)
+                    .unwrap_or(0) as usize,
+            )
+            .and_then(|view| view.get("byteStride"))
+            .and_then(Json::as_u64)
+            .unwrap_or(12) as u32;
+
+        mesh_assets.insert(
+            asset_id,
+            MeshAsset {
+                id: asset_id,
+                name: None,
+                vertex_count: (positions.len() / 3) as u32,
+                index_count: indices.len() as u32,
+                index_format,
+                topology,
+                layout: BufferLayout {
+                    position: BufferLayoutEntry { offset: 0, stride },
+                    normal: None,
+                    uv: None,
+                },
+                source_uri: None,
+                bounds: Some(mesh_bounds(&positions).into()),
+            },
+        );
+        meshes.insert(asset_id, Mesh { positions, indices });
+    }
+
+    let mut material_assets = BTreeMap::new();
+    let mut material_components = BTreeMap::new();
+    for (index, material) in materials.iter().enumerate() {
+        let pbr = material.get("pbrMetallicRoughness");
+        let base_color = pbr
+            .and_then(|p| p.get("baseColorFactor"))
+            .and_then(Json::as_array)
+            .map(|values| {
+                let mut out = [1.0f32; 4];
+                for (slot, value) in out.iter_mut().zip(values.iter()) {
+                    *slot = value.as_f64().unwrap_or(1.0) as f32;
+                }
+                out
+            })
+            .unwrap_or([1.0, 1.0, 1.0, 1.0]);
+        let metallic = pbr
+            .and_then(|p| p.get("metallicFactor"))
+            .and_then(Json::as_f64)
+            .unwrap_or(1.0) as f32;
+        let roughness = pbr
+            .and_then(|p| p.get("roughnessFactor"))
+            .and_then(Json::as_f64)
+            .unwrap_or(1.0) as f32;
+        let alpha_mode = material.get("alphaMode").and_then(Json::as_str).unwrap_or("OPAQUE");
+        let opacity = if alpha_mode == "OPAQUE" { 1.0 } else { base_color[3] };
+
+        material_assets.insert(
+            index as ComponentId,
+            MaterialAsset {
+                id: index as AssetId,
+                name: None,
+                base_color,
+            },
+        );
+        material_components.insert(
+            index as ComponentId,
+            MaterialComponent {
+                base_color,
+                metallic,
+                roughness,
+                opacity,
+            },
+        );
+    }
+
+    let mut texture_assets = BTreeMap::new();
+    for (index, texture) in textures.iter().enumerate() {
+        let uri = texture.get("uri").and_then(Json::as_str).unwrap_or_default().to_string();
+        texture_assets.insert(
+            index as AssetId,
+            TextureAsset {
+                id: index as AssetId,
+                name: None,
+                uri,
+            },
+        );
+    }
+
+    let root_scale = nodes
+        .first()
+        .and_then(|n| n.get("scale"))
+        .and_then(Json::as_array)
+        .and_then(|s| s.first())
+        .and_then(Json::as_f64)
+        .unwrap_or(1.0);
+
+    let mut entities = Vec::new();
+    let mut transforms = BTreeMap::new();
+    let mut geometries = BTreeMap::new();
+    let mut materials = BTreeMap::new();
+
+    for (node_index, node) in nodes.iter().enumerate().skip(1) {
+        let position = node
+            .get("translation")
+            .and_then(Json::as_array)
+            .map(array_to_vector3)
+            .unwrap_or_else(Vector3::zero);
+        let rotation = node
+            .get("rotation")
+            .and_then(Json::as_array)
+            .map(|values| Quaternion {
+                x: values[0].as_f64().unwrap_or(0.0),
+                y: values[1].as_f64().unwrap_or(0.0),
+                z: values[2].as_f64().unwrap_or(0.0),
+                w: values[3].as_f64().unwrap_or(1.0),
+            })
+            .unwrap_or_else(Quaternion::identity);
+        let scale = node
+            .get("scale")
+            .and_then(Json::as_array)
+            .map(array_to_vector3)
+            .unwrap_or(Vector3::new(1.0, 1.0, 1.0));
+
+        let entity_id = node_index as EntityId;
+        transforms.insert(
+            entity_id,
+            TransformComponent {
+                position,
+                rotation: Rotation::Quaternion(rotation),
+                scale,
+            },
+        );
+
+        let geometry = node.get("mesh").and_then(Json::as_u64).map(|mesh_index| {
+            geometries.insert(
+                entity_id,
+                GeometryComponent {
+                    mesh: mesh_index,
+                    topology: mesh_assets
+                        .get(&mesh_index)
+                        .map(|m| m.topology)
+                        .unwrap_or(GeometryTopology::Triangles),
+                    local_bounds: mesh_assets.get(&mesh_index).and_then(|m| m.bounds),
+                },
+            );
+            entity_id
+        });
+
+        let material = geometry.and_then(|_| {
+            let mesh_index = node.get("mesh").and_then(Json::as_u64)?;
+            let material_index = material_by_mesh_asset.get(&mesh_index)?;
+            let material_component = material_components.get(material_index)?;
+            materials.insert(entity_id, *material_component);
+            Some(entity_id)
+        });
+
+        entities.push(EntityRecord {
+            id: entity_id,
+            name: node.get("name").and_then(Json::as_str).map(str::to_string),
+            components: ComponentRefs {
+                transform: Some(entity_id),
+                geometry,
+                material,
+                layer: None,
+                metadata: None,
+            },
+        });
+    }
+
+    let scene = SceneGraph {
+        schema_version: SchemaVersion::V0,
+        metadata: SceneMetadata {
+            name: nodes
+                .first()
+                .and_then(|n| n.get("name"))
+                .and_then(Json::as_str)
+                .unwrap_or("imported")
+                .to_string(),
+            description: None,
+            unit: meters_to_unit(root_scale),
+            up_axis: crate::Axis::Y,
+            created_at: String::new(),
+            updated_at: String::new(),
+        },
+        entities,
+        components: ComponentTable {
+            transforms,
+            geometries,
+            materials,
+            layers: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+        },
+        assets: AssetRegistry {
+            meshes: mesh_assets,
+            materials: material_assets,
+            textures: texture_assets,
+        },
+    };
+
+    Ok((scene, meshes))
+}
+
+fn array_to_vector3(values: &[Json]) -> Vector3 {
+    Vector3::new(
+        values.first().and_then(Json::as_f64).unwrap_or(0.0),
+        values.get(1).and_then(Json::as_f64).unwrap_or(0.0),
+        values.get(2).and_then(Json::as_f64).unwrap_or(0.0),
+    )
+}
+
+impl From<(Vector3, Vector3)> for Aabb {
+    fn from((min, max): (Vector3, Vector3)) -> Self {
+        Aabb { min, max }
+    }
+}
+
+// --- A minimal JSON value, parser, and writer. glTF's document model is
+// plain JSON; rather than pull in an external serialization crate, this
+// mirrors the hand-rolled approach the rest of the kernel takes (see
+// `Matrix4`, marching cubes) and implements just enough of the spec for
+// the subset of glTF this module reads and writes.
+#[derive(Clone, Debug, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+fn json_object<const N: usize>(fields: [(&str, Json); N]) -> Json {
+    Json::Object(fields.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+}
+
+fn json_object_vec(fields: Vec<(&str, Json)>) -> Json {
+    Json::Object(fields.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    fn as_u64(&self) -> Option<u64> {
+        self.as_f64().map(|n| n as u64)
+    }
+
+    fn parse(input: &str) -> Result<Json, String> {
+        let mut chars: Vec<char> = input.chars().collect();
+        chars.reverse();
+        let value = parse_value(&mut chars)?;
+        Ok(value)
+    }
+
+    fn write(&self, out: &mut String) {
+        match self {
+            Json::Null => out.push_str("null"),
+            Json::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+            Json::Number(n) => out.push_str(&format!("{n}")),
+            Json::String(s) => {
+                out.push('"');
+                for c in s.chars() {
+                    match c {
+                        '"' => out.push_str("\\\""),
+                        '\\' => out.push_str("\\\\"),
+                        '\n' => out.push_str("\\n"),
+                        _ => out.push(c),
+                    }
+                }
+                out.push('"');
+            }
+            Json::Array(values) => {
+                out.push('[');
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    value.write(out);
+                }
+                out.push(']');
+            }
+            Json::Object(fields) => {
+                out.push('{');
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    Json::String(key.clone()).write(out);
+                    out.push(':');
+                    value.write(out);
+                }
+                out.push('}');
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Json {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut out = String::new();
+        self.write(&mut out);
+        f.write_str(&out)
+    }
+}
+
+fn skip_whitespace(chars: &mut Vec<char>) {
+    while matches!(chars.last(), Some(c) if c.is_whitespace()) {
+        chars.pop();
+    }
+}
+
+fn parse_value(chars: &mut Vec<char>) -> Result<Json, String> {
+    skip_whitespace(chars);
+    match chars.last() {
+        Some('{') => parse_object(chars),
+        Some('[') => parse_array(chars),
+        Some('"') => Ok(Json::String(parse_string(chars)?)),
+        Some('t') => parse_literal(chars, "true", Json::Bool(true)),
+        Some('f') => parse_literal(chars, "false", Json::Bool(false)),
+        Some('n') => parse_literal(chars, "null", Json::Null),
+        Some(c) if c.is_ascii_digit() || *c == '-' => parse_number(chars),
+        other => Err(format!("unexpected token: {other:?}")),
+    }
+}
+
+fn parse_literal(chars: &mut Vec<char>, literal: &str, value: Json) -> Result<Json, String> {
+    for expected in literal.chars() {
+        if chars.pop() != Some(expected) {
+            return Err(format!("expected literal `{literal}`"));
+        }
+    }
+    Ok(value)
+}
+
+fn parse_number(chars: &mut Vec<char>) -> Result<Json, String> {
+    let mut text = String::new();
+    while matches!(chars.last(), Some(c) if c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')) {
+        text.push(chars.pop().unwrap());
+    }
+    text.parse::<f64>()
+        .map(Json::Number)
+        .map_err(|e| format!("invalid number `{text}`: {e}"))
+}
+
+fn parse_string(chars: &mut Vec<char>) -> Result<String, String> {
+    chars.pop(); // opening quote
+    let mut out = String::new();
+    loop {
+        match chars.pop() {
+            Some('"') => return Ok(out),
+            Some('\\') => match chars.pop() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some(c) => out.push(c),
+                None => return Err("unterminated escape".to_string()),
+            },
+            Some(c) => out.push(c),
+            None => return Err("unterminated string".to_string()),
+        }
+    }
+}
+
+fn parse_array(chars: &mut Vec<char>) -> Result<Json, String> {
+    chars.pop(); // '['
+    let mut values = Vec::new();
+    skip_whitespace(chars);
+    if chars.last() == Some(&']') {
+        chars.pop();
+        return Ok(Json::Array(values));
+    }
+    loop {
+        values.push(parse_value(chars)?);
+        skip_whitespace(chars);
+        match chars.pop() {
+            Some(',') => continue,
+            Some(']') => break,
+            other => return Err(format!("expected `,` or `]`, got {other:?}")),
+        }
+    }
+    Ok(Json::Array(values))
+}
+
+fn parse_object(chars: &mut Vec<char>) -> Result<Json, String> {
+    chars.pop(); // '{'
+    let mut fields = Vec::new();
+    skip_whitespace(chars);
+    if chars.last() == Some(&'}') {
+        chars.pop();
+        return Ok(Json::Object(fields));
+    }
+    loop {
+        skip_whitespace(chars);
+        let key = parse_string(chars)?;
+        skip_whitespace(chars);
+        if chars.pop() != Some(':') {
+            return Err("expected `:`".to_string());
+        }
+        let value = parse_value(chars)?;
+        fields.push((key, value));
+        skip_whitespace(chars);
+        match chars.pop() {
+            Some(',') => continue,
+            Some('}') => break,
+            other => return Err(format!("expected `,` or `}}`, got {other:?}")),
+        }
+    }
+    Ok(Json::Object(fields))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_scene() -> (SceneGraph, MeshData) {
+        let mesh_id: AssetId = 1;
+        let mesh = Mesh::cube(2.0);
+        let mut mesh_assets = BTreeMap::new();
+        mesh_assets.insert(
+            mesh_id,
+            MeshAsset {
+                id: mesh_id,
+                name: Some("cube".to_string()),
+                vertex_count: (mesh.positions.len() / 3) as u32,
+                index_count: mesh.indices.len() as u32,
+                index_format: IndexFormat::Uint32,
+                topology: GeometryTopology::Triangles,
+                layout: BufferLayout {
+                    position: BufferLayoutEntry { offset: 0, stride: 12 },
+                    normal: None,
+                    uv: None,
+                },
+                source_uri: None,
+                bounds: Some(mesh_bounds(&mesh.positions).into()),
+            },
+        );
+
+        let entity_id: EntityId = 1;
+        let mut transforms = BTreeMap::new();
+        transforms.insert(
+            1 as ComponentId,
+            TransformComponent {
+                position: Vector3::new(1.0, 2.0, 3.0),
+                rotation: Rotation::Quaternion(Quaternion::from_axis_angle(
+                    Vector3::new(0.0, 1.0, 0.0),
+                    0.5,
+                )),
+                scale: Vector3::new(1.0, 1.0, 1.0),
+            },
+        );
+        let mut geometries = BTreeMap::new();
+        geometries.insert(
+            1 as ComponentId,
+            GeometryComponent {
+                mesh: mesh_id,
+                topology: GeometryTopology::Triangles,
+                local_bounds: None,
+            },
+        );
+
+        let scene = SceneGraph {
+            schema_version: SchemaVersion::V0,
+            metadata: SceneMetadata {
+                name: "scene".to_string(),
+                description: None,
+                unit: Unit::M,
+                up_axis: crate::Axis::Y,
+                created_at: "0".to_string(),
+                updated_at: "0".to_string(),
+            },
+            entities: vec![EntityRecord {
+                id: entity_id,
+                name: Some("cube entity".to_string()),
+                components: ComponentRefs {
+                    transform: Some(1),
+                    geometry: Some(1),
+                    material: None,
+                    layer: None,
+                    metadata: None,
+                },
+            }],
+            components: ComponentTable {
+                transforms,
+                geometries,
+                materials: BTreeMap::new(),
+                layers: BTreeMap::new(),
+                metadata: BTreeMap::new(),
+            },
+            assets: AssetRegistry {
+                meshes: mesh_assets,
+                materials: BTreeMap::new(),
+                textures: BTreeMap::new(),
+            },
+        };
+
+        let mut meshes = MeshData::new();
+        meshes.insert(mesh_id, mesh);
+        (scene, meshes)
+    }
+
+    #[test]
+    fn export_then_import_round_trips_mesh_data() {
+        let (scene, meshes) = sample_scene();
+        let document = export(&scene, &meshes);
+        let (imported_scene, imported_meshes) = import(&document.json, &document.buffer).unwrap();
+
+        assert_eq!(imported_scene.entities.len(), 1);
+        let imported_mesh = imported_meshes.values().next().unwrap();
+        let original_mesh = meshes.values().next().unwrap();
+        assert_eq!(imported_mesh.indices, original_mesh.indices);
+        assert_eq!(imported_mesh.positions.len(), original_mesh.positions.len());
+    }
+
+    #[test]
+    fn export_then_import_preserves_transform() {
+        let (scene, meshes) = sample_scene();
+        let document = export(&scene, &meshes);
+        let (imported_scene, _) = import(&document.json, &document.buffer).unwrap();
+
+        let original_transform = scene.components.transforms.get(&1).unwrap();
+        let imported_transform = imported_scene.components.transforms.values().next().unwrap();
+        assert!((original_transform.position.x - imported_transform.position.x).abs() < 1e-5);
+        assert!((original_transform.position.y - imported_transform.position.y).abs() < 1e-5);
+        assert!((original_transform.position.z - imported_transform.position.z).abs() < 1e-5);
+    }
+
+    #[test]
+    fn json_round_trips_through_parser_and_writer() {
+        let value = json_object([
+            ("a", Json::Number(1.0)),
+            ("b", Json::Array(vec![Json::Bool(true), Json::Null])),
+            ("c", Json::String("hi".to_string())),
+        ]);
+        let text = value.to_string();
+        let parsed = Json::parse(&text).unwrap();
+        assert_eq!(parsed.get("a").and_then(Json::as_f64), Some(1.0));
+        assert_eq!(parsed.get("c").and_then(Json::as_str), Some("hi"));
+    }
+}
@@ -1,6 +1,18 @@
+mod animation;
+mod gltf;
+mod picking;
+mod plane_split;
+mod quaternion;
 mod scene;
+mod sdf;
 
+pub use animation::*;
+pub use gltf::*;
+pub use picking::*;
+pub use plane_split::*;
+pub use quaternion::*;
 pub use scene::*;
+pub use sdf::*;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Vector3 {
@@ -167,6 +179,87 @@ impl Matrix4 {
         Self { elements }
     }
 
+    pub fn transpose(self) -> Self {
+        let e = self.elements;
+        Self {
+            elements: [
+                e[0], e[4], e[8], e[12], e[1], e[5], e[9], e[13], e[2], e[6],
+                e[10], e[14], e[3], e[7], e[11], e[15],
+            ],
+        }
+    }
+
+    /// Inverts the matrix via cofactor expansion, returning `None` when the
+    /// determinant is too close to zero to invert reliably.
+    pub fn invert(self) -> Option<Self> {
+        let e = self.elements;
+
+        let s0 = e[0] * e[5] - e[1] * e[4];
+        let s1 = e[0] * e[6] - e[2] * e[4];
+        let s2 = e[0] * e[7] - e[3] * e[4];
+        let s3 = e[1] * e[6] - e[2] * e[5];
+        let s4 = e[1] * e[7] - e[3] * e[5];
+        let s5 = e[2] * e[7] - e[3] * e[6];
+
+        let c0 = e[8] * e[13] - e[9] * e[12];
+        let c1 = e[8] * e[14] - e[10] * e[12];
+        let c2 = e[8] * e[15] - e[11] * e[12];
+        let c3 = e[9] * e[14] - e[10] * e[13];
+        let c4 = e[9] * e[15] - e[11] * e[13];
+        let c5 = e[10] * e[15] - e[11] * e[14];
+
+        let det = s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0;
+        if det.abs() < 1e-12 {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+
+        let mut out = [0.0; 16];
+        out[0] = (e[5] * c5 - e[6] * c4 + e[7] * c3) * inv_det;
+        out[1] = (-e[1] * c5 + e[2] * c4 - e[3] * c3) * inv_det;
+        out[2] = (e[13] * s5 - e[14] * s4 + e[15] * s3) * inv_det;
+        out[3] = (-e[9] * s5 + e[10] * s4 - e[11] * s3) * inv_det;
+
+        out[4] = (-e[4] * c5 + e[6] * c2 - e[7] * c1) * inv_det;
+        out[5] = (e[0] * c5 - e[2] * c2 + e[3] * c1) * inv_det;
+        out[6] = (-e[12] * s5 + e[14] * s2 - e[15] * s1) * inv_det;
+        out[7] = (e[8] * s5 - e[10] * s2 + e[11] * s1) * inv_det;
+
+        out[8] = (e[4] * c4 - e[5] * c2 + e[7] * c0) * inv_det;
+        out[9] = (-e[0] * c4 + e[1] * c2 - e[3] * c0) * inv_det;
+        out[10] = (e[12] * s4 - e[13] * s2 + e[15] * s0) * inv_det;
+        out[11] = (-e[8] * s4 + e[9] * s2 - e[11] * s0) * inv_det;
+
+        out[12] = (-e[4] * c3 + e[5] * c1 - e[6] * c0) * inv_det;
+        out[13] = (e[0] * c3 - e[1] * c1 + e[2] * c0) * inv_det;
+        out[14] = (-e[12] * s3 + e[13] * s1 - e[14] * s0) * inv_det;
+        out[15] = (e[8] * s3 - e[9] * s1 + e[10] * s0) * inv_det;
+
+        Some(Self { elements: out })
+    }
+
+    /// Returns the inverse-transpose of the upper-left 3x3 block, for
+    /// transforming normals under non-uniform scale.
+    pub fn normal_matrix(self) -> Option<Self> {
+        let mut upper = Self::identity();
+        for column in 0..3 {
+            for row in 0..3 {
+                upper.elements[column * 4 + row] = self.elements[column * 4 + row];
+            }
+        }
+        let inv = upper.invert()?;
+        Some(inv.transpose())
+    }
+
+    pub fn transform_point(self, point: Vector3) -> Vector3 {
+        let e = self.elements;
+        Vector3::new(
+            e[0] * point.x + e[4] * point.y + e[8] * point.z + e[12],
+            e[1] * point.x + e[5] * point.y + e[9] * point.z + e[13],
+            e[2] * point.x + e[6] * point.y + e[10] * point.z + e[14],
+        )
+    }
+
     pub fn compose(position: Vector3, rotation: Vector3, scale: Vector3) -> Self {
         let (sx, cx) = rotation.x.sin_cos();
         let (sy, cy) = rotation.y.sin_cos();
@@ -198,6 +291,29 @@ impl Matrix4 {
         elements[15] = 1.0;
         Self { elements }
     }
+
+    /// Like `compose`, but takes a `Quaternion` rotation instead of Euler
+    /// angles, for callers that carry rotation as a quaternion to avoid
+    /// gimbal lock.
+    pub fn compose_quat(position: Vector3, rotation: Quaternion, scale: Vector3) -> Self {
+        let rotation = rotation.to_matrix4().elements;
+
+        let mut elements = [0.0; 16];
+        elements[0] = rotation[0] * scale.x;
+        elements[1] = rotation[1] * scale.x;
+        elements[2] = rotation[2] * scale.x;
+        elements[4] = rotation[4] * scale.y;
+        elements[5] = rotation[5] * scale.y;
+        elements[6] = rotation[6] * scale.y;
+        elements[8] = rotation[8] * scale.z;
+        elements[9] = rotation[9] * scale.z;
+        elements[10] = rotation[10] * scale.z;
+        elements[12] = position.x;
+        elements[13] = position.y;
+        elements[14] = position.z;
+        elements[15] = 1.0;
+        Self { elements }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -256,4 +372,44 @@ mod tests {
         let right = Matrix4::multiply(b, a);
         assert_eq!(right.elements, b.elements);
     }
+
+    #[test]
+    fn matrix_transpose_round_trips() {
+        let m = Matrix4 {
+            elements: [
+                1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0,
+                13.0, 14.0, 15.0, 16.0,
+            ],
+        };
+        assert_eq!(m.transpose().transpose(), m);
+    }
+
+    #[test]
+    fn matrix_invert_identity() {
+        let inv = Matrix4::identity().invert().unwrap();
+        assert_eq!(inv, Matrix4::identity());
+    }
+
+    #[test]
+    fn matrix_invert_composed() {
+        let m = Matrix4::compose(
+            Vector3::new(1.0, 2.0, 3.0),
+            Vector3::new(0.3, 0.1, 0.7),
+            Vector3::new(2.0, 1.0, 0.5),
+        );
+        let inv = m.invert().unwrap();
+        let product = Matrix4::multiply(m, inv);
+        let identity = Matrix4::identity();
+        for (got, want) in product.elements.iter().zip(identity.elements.iter()) {
+            assert!((got - want).abs() < 1e-9, "{got} != {want}");
+        }
+    }
+
+    #[test]
+    fn matrix_invert_singular_is_none() {
+        let m = Matrix4 {
+            elements: [0.0; 16],
+        };
+        assert_eq!(m.invert(), None);
+    }
 }
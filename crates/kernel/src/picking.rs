@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+
+use crate::{AssetId, EntityId, Matrix4, Mesh, SceneGraph, Vector3};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ray {
+    pub origin: Vector3,
+    pub direction: Vector3,
+}
+
+impl Ray {
+    pub fn new(origin: Vector3, direction: Vector3) -> Self {
+        Self {
+            origin,
+            direction: direction.normalize(),
+        }
+    }
+
+    pub fn at(self, t: f64) -> Vector3 {
+        self.origin.add(self.direction.scale(t))
+    }
+
+    /// Slab test against an axis-aligned box in the same space as the ray.
+    /// Returns the entry distance `t` if the ray hits, even if the origin
+    /// is already inside the box (in which case `t` may be negative).
+    fn intersects_aabb(self, aabb: crate::Aabb) -> Option<f64> {
+        let mut t_min = f64::NEG_INFINITY;
+        let mut t_max = f64::INFINITY;
+
+        for axis in 0..3 {
+            let (origin, dir, min, max) = match axis {
+                0 => (self.origin.x, self.direction.x, aabb.min.x, aabb.max.x),
+                1 => (self.origin.y, self.direction.y, aabb.min.y, aabb.max.y),
+                _ => (self.origin.z, self.direction.z, aabb.min.z, aabb.max.z),
+            };
+
+            if dir.abs() < 1e-12 {
+                if origin < min || origin > max {
+                    return None;
+                }
+                continue;
+            }
+
+            let inv_dir = 1.0 / dir;
+            let mut t0 = (min - origin) * inv_dir;
+            let mut t1 = (max - origin) * inv_dir;
+            if t0 > t1 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            t_min = t_min.max(t0);
+            t_max = t_max.min(t1);
+            if t_min > t_max {
+                return None;
+            }
+        }
+
+        if t_max < 0.0 {
+            return None;
+        }
+        Some(t_min)
+    }
+}
+
+/// Möller–Trumbore ray-triangle intersection. Returns the distance along
+/// the ray to the hit point, or `None` if the ray misses or the hit is
+/// behind the origin.
+fn intersect_triangle(ray: Ray, v0: Vector3, v1: Vector3, v2: Vector3) -> Option<f64> {
+    let edge1 = v1.sub(v0);
+    let edge2 = v2.sub(v0);
+    let p = ray.direction.cross(edge2);
+    let det = edge1.dot(p);
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+
+    let t_vec = ray.origin.sub(v0);
+    let u = t_vec.dot(p) * inv_det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let q = t_vec.cross(edge1);
+    let v = ray.direction.dot(q) * inv_det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    let t = edge2.dot(q) * inv_det;
+    if t <= 0.0 {
+        return None;
+    }
+
+    Some(t)
+}
+
+fn mesh_triangles<'a>(
+    mesh: &'a Mesh,
+    world: &'a Matrix4,
+) -> impl Iterator<Item = (Vector3, Vector3, Vector3)> + 'a {
+    let vertex = move |index: u32| {
+        let i = index as usize * 3;
+        world.transform_point(Vector3::new(
+            mesh.positions[i] as f64,
+            mesh.positions[i + 1] as f64,
+            mesh.positions[i + 2] as f64,
+        ))
+    };
+    mesh.indices
+        .chunks_exact(3)
+        .map(move |tri| (vertex(tri[0]), vertex(tri[1]), vertex(tri[2])))
+}
+
+/// Casts `ray` against every entity in `scene` and returns the nearest hit
+/// as `(entity, t, point)`. `meshes` supplies the raw vertex/index data for
+/// each geometry asset, since `AssetRegistry` only tracks mesh metadata.
+/// Each entity's world `Aabb` is used as a broad-phase reject before the
+/// exact per-triangle test.
+pub fn pick(scene: &SceneGraph, meshes: &BTreeMap<AssetId, Mesh>, ray: Ray) -> Option<(EntityId, f64, Vector3)> {
+    let mut best: Option<(EntityId, f64, Vector3)> = None;
+
+    for entity in &scene.entities {
+        let Some(geometry_id) = entity.components.geometry else {
+            continue;
+        };
+        let Some(geometry) = scene.components.geometries.get(&geometry_id) else {
+            continue;
+        };
+        let Some(mesh) = meshes.get(&geometry.mesh) else {
+            continue;
+        };
+
+        let transform = entity
+            .components
+            .transform
+            .and_then(|id| scene.components.transforms.get(&id))
+            .copied()
+            .unwrap_or_else(crate::TransformComponent::identity);
+        let world = transform.to_matrix4();
+
+        if let Some(local_bounds) = geometry
+            .local_bounds
+            .or_else(|| scene.assets.meshes.get(&geometry.mesh).and_then(|m| m.bounds))
+        {
+            let world_bounds = local_bounds.transform(&world);
+            if ray.intersects_aabb(world_bounds).is_none() {
+                continue;
+            }
+        }
+
+        for (v0, v1, v2) in mesh_triangles(mesh, &world) {
+            if let Some(t) = intersect_triangle(ray, v0, v1, v2) {
+                if best.map(|(_, best_t, _)| t < best_t).unwrap_or(true) {
+                    best = Some((entity.id, t, ray.at(t)));
+                }
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ray_hits_unit_cube() {
+        let mesh = Mesh::cube(2.0);
+        let identity = Matrix4::identity();
+        let ray = Ray::new(Vector3::new(0.0, 0.0, 5.0), Vector3::new(0.0, 0.0, -1.0));
+
+        let hit = mesh_triangles(&mesh, &identity)
+            .filter_map(|(v0, v1, v2)| intersect_triangle(ray, v0, v1, v2))
+            .fold(None, |best: Option<f64>, t| {
+                Some(best.map_or(t, |b| b.min(t)))
+            });
+
+        assert!(hit.is_some());
+        assert!((hit.unwrap() - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ray_misses_cube() {
+        let mesh = Mesh::cube(2.0);
+        let identity = Matrix4::identity();
+        let ray = Ray::new(Vector3::new(10.0, 10.0, 5.0), Vector3::new(0.0, 0.0, -1.0));
+
+        let hit = mesh_triangles(&mesh, &identity)
+            .filter_map(|(v0, v1, v2)| intersect_triangle(ray, v0, v1, v2))
+            .next();
+        assert!(hit.is_none());
+    }
+
+    #[test]
+    fn ray_intersects_aabb_slab_test() {
+        let aabb = crate::Aabb {
+            min: Vector3::new(-1.0, -1.0, -1.0),
+            max: Vector3::new(1.0, 1.0, 1.0),
+        };
+        let hit = Ray::new(Vector3::new(0.0, 0.0, 5.0), Vector3::new(0.0, 0.0, -1.0));
+        assert!(hit.intersects_aabb(aabb).is_some());
+
+        let miss = Ray::new(Vector3::new(10.0, 10.0, 5.0), Vector3::new(0.0, 0.0, -1.0));
+        assert!(miss.intersects_aabb(aabb).is_none());
+    }
+}
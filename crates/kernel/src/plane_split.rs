@@ -0,0 +1,250 @@
+use crate::{EntityId, Vector3};
+
+/// A splitting plane in point-normal form: a point on the plane plus its
+/// (not necessarily unit-length) normal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Plane {
+    pub point: Vector3,
+    pub normal: Vector3,
+}
+
+impl Plane {
+    /// Signed distance from `p` to the plane, scaled by the normal's
+    /// length; positive on the side the normal points toward.
+    fn signed_distance(self, p: Vector3) -> f64 {
+        self.normal.dot(p.sub(self.point))
+    }
+}
+
+/// A convex, planar polygon carried through the BSP build tagged with the
+/// entity it originated from, so split fragments can still be resolved
+/// back to their source.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polygon {
+    pub vertices: Vec<Vector3>,
+    pub source: EntityId,
+}
+
+const EPSILON: f64 = 1e-9;
+
+enum Side {
+    Coplanar,
+    Front,
+    Back,
+    Spanning,
+}
+
+impl Polygon {
+    fn plane(&self) -> Plane {
+        let point = self.vertices[0];
+        let normal = self.vertices[1]
+            .sub(point)
+            .cross(self.vertices[2].sub(point))
+            .normalize();
+        Plane { point, normal }
+    }
+
+    fn classify(&self, plane: Plane) -> Side {
+        let mut has_front = false;
+        let mut has_back = false;
+        for &v in &self.vertices {
+            match plane.signed_distance(v) {
+                d if d > EPSILON => has_front = true,
+                d if d < -EPSILON => has_back = true,
+                _ => {}
+            }
+        }
+        match (has_front, has_back) {
+            (false, false) => Side::Coplanar,
+            (true, false) => Side::Front,
+            (false, true) => Side::Back,
+            (true, true) => Side::Spanning,
+        }
+    }
+
+    /// Clips a polygon known to straddle `plane` into `(front, back)`
+    /// fragments, split at the plane intersection and each still tagged
+    /// with the original source entity.
+    fn split(&self, plane: Plane) -> (Polygon, Polygon) {
+        let distances: Vec<f64> = self
+            .vertices
+            .iter()
+            .map(|&v| plane.signed_distance(v))
+            .collect();
+
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+
+        for i in 0..self.vertices.len() {
+            let j = (i + 1) % self.vertices.len();
+            let (a, b) = (self.vertices[i], self.vertices[j]);
+            let (da, db) = (distances[i], distances[j]);
+
+            if da >= -EPSILON {
+                front.push(a);
+            }
+            if da <= EPSILON {
+                back.push(a);
+            }
+
+            if (da > EPSILON && db < -EPSILON) || (da < -EPSILON && db > EPSILON) {
+                let t = da / (da - db);
+                let intersection = a.add(b.sub(a).scale(t));
+                front.push(intersection);
+                back.push(intersection);
+            }
+        }
+
+        (
+            Polygon {
+                vertices: front,
+                source: self.source,
+            },
+            Polygon {
+                vertices: back,
+                source: self.source,
+            },
+        )
+    }
+}
+
+/// A binary space partitioning tree over a set of `Polygon`s. Each node
+/// splits space with one polygon's plane; polygons coplanar with that
+/// plane are stored on the node, and straddling polygons are divided into
+/// front/back fragments before recursing.
+struct BspNode {
+    plane: Plane,
+    coplanar: Vec<Polygon>,
+    front: Option<Box<BspNode>>,
+    back: Option<Box<BspNode>>,
+}
+
+impl BspNode {
+    fn build(mut polygons: Vec<Polygon>) -> Option<Box<BspNode>> {
+        if polygons.is_empty() {
+            return None;
+        }
+
+        let root = polygons.remove(0);
+        let plane = root.plane();
+        let mut coplanar = vec![root];
+        let mut front_polys = Vec::new();
+        let mut back_polys = Vec::new();
+
+        for polygon in polygons {
+            match polygon.classify(plane) {
+                Side::Coplanar => coplanar.push(polygon),
+                Side::Front => front_polys.push(polygon),
+                Side::Back => back_polys.push(polygon),
+                Side::Spanning => {
+                    let (front, back) = polygon.split(plane);
+                    front_polys.push(front);
+                    back_polys.push(back);
+                }
+            }
+        }
+
+        Some(Box::new(BspNode {
+            plane,
+            coplanar,
+            front: BspNode::build(front_polys),
+            back: BspNode::build(back_polys),
+        }))
+    }
+
+    /// Appends the entities visited under this node to `out` in
+    /// back-to-front order as seen from `eye`.
+    fn order_back_to_front(&self, eye: Vector3, out: &mut Vec<EntityId>) {
+        let eye_in_front = self.plane.signed_distance(eye) >= 0.0;
+        let (near, far) = if eye_in_front {
+            (&self.front, &self.back)
+        } else {
+            (&self.back, &self.front)
+        };
+
+        if let Some(node) = far {
+            node.order_back_to_front(eye, out);
+        }
+        out.extend(self.coplanar.iter().map(|polygon| polygon.source));
+        if let Some(node) = near {
+            node.order_back_to_front(eye, out);
+        }
+    }
+}
+
+/// Builds a BSP tree from `polygons` and returns the source entities in
+/// back-to-front draw order as seen from `eye`, splitting fragments across
+/// straddling planes so overlapping geometry sorts correctly even when no
+/// single distance ordering would work. An entity contributing more than
+/// one polygon (e.g. the faces of a bounding box) appears once, at its
+/// first (farthest) occurrence.
+pub fn order_back_to_front(polygons: Vec<Polygon>, eye: Vector3) -> Vec<EntityId> {
+    let mut fragments = Vec::new();
+    if let Some(root) = BspNode::build(polygons) {
+        root.order_back_to_front(eye, &mut fragments);
+    }
+
+    let mut seen = std::collections::BTreeSet::new();
+    fragments
+        .into_iter()
+        .filter(|entity| seen.insert(*entity))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quad(y: f64, z: f64, source: EntityId) -> Polygon {
+        Polygon {
+            vertices: vec![
+                Vector3::new(-1.0, y, z),
+                Vector3::new(1.0, y, z),
+                Vector3::new(1.0, y + 1.0, z),
+                Vector3::new(-1.0, y + 1.0, z),
+            ],
+            source,
+        }
+    }
+
+    #[test]
+    fn orders_parallel_quads_back_to_front() {
+        let near = quad(0.0, 5.0, 1);
+        let far = quad(0.0, -5.0, 2);
+        let eye = Vector3::new(0.0, 0.0, 10.0);
+
+        let order = order_back_to_front(vec![near, far], eye);
+        assert_eq!(order, vec![2, 1]);
+    }
+
+    #[test]
+    fn reorders_when_eye_moves_to_other_side() {
+        let a = quad(0.0, 5.0, 1);
+        let b = quad(0.0, -5.0, 2);
+        let eye = Vector3::new(0.0, 0.0, -10.0);
+
+        let order = order_back_to_front(vec![a, b], eye);
+        assert_eq!(order, vec![1, 2]);
+    }
+
+    #[test]
+    fn splits_straddling_polygon_into_both_sides() {
+        let straddler = Polygon {
+            vertices: vec![
+                Vector3::new(-1.0, 0.0, -5.0),
+                Vector3::new(1.0, 0.0, -5.0),
+                Vector3::new(1.0, 0.0, 5.0),
+                Vector3::new(-1.0, 0.0, 5.0),
+            ],
+            source: 1,
+        };
+        let splitter = quad(-5.0, 0.0, 2);
+
+        let plane = splitter.plane();
+        assert!(matches!(straddler.classify(plane), Side::Spanning));
+
+        let (front, back) = straddler.split(plane);
+        assert!(front.vertices.len() >= 3);
+        assert!(back.vertices.len() >= 3);
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{Matrix4, Vector3};
+
+/// A unit quaternion rotation, used in place of Euler angles wherever
+/// gimbal lock or smooth interpolation matters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quaternion {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+impl Quaternion {
+    pub fn identity() -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 1.0,
+        }
+    }
+
+    pub fn from_axis_angle(axis: Vector3, angle: f64) -> Self {
+        let axis = axis.normalize();
+        let (s, c) = (angle * 0.5).sin_cos();
+        Self {
+            x: axis.x * s,
+            y: axis.y * s,
+            z: axis.z * s,
+            w: c,
+        }
+    }
+
+    /// Builds a quaternion from Euler angles applied in XYZ order, matching
+    /// the rotation `Matrix4::compose` derives from the same angles.
+    pub fn from_euler(euler: Vector3) -> Self {
+        let x_axis = Self::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), euler.x);
+        let y_axis = Self::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), euler.y);
+        let z_axis = Self::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), euler.z);
+        z_axis.multiply(y_axis).multiply(x_axis)
+    }
+
+    pub fn multiply(self, other: Self) -> Self {
+        Self {
+            x: self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+            y: self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+            z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w,
+            w: self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
+        }
+    }
+
+    pub fn dot(self, other: Self) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    pub fn length(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn normalize(self) -> Self {
+        let len = self.length();
+        if len > 0.0 {
+            Self {
+                x: self.x / len,
+                y: self.y / len,
+                z: self.z / len,
+                w: self.w / len,
+            }
+        } else {
+            Self::identity()
+        }
+    }
+
+    pub fn rotate_vector(self, v: Vector3) -> Vector3 {
+        let qv = Vector3::new(self.x, self.y, self.z);
+        let t = qv.cross(v).scale(2.0);
+        v.add(t.scale(self.w)).add(qv.cross(t))
+    }
+
+    pub fn to_matrix4(self) -> Matrix4 {
+        let q = self.normalize();
+        let (x, y, z, w) = (q.x, q.y, q.z, q.w);
+
+        let mut elements = [0.0; 16];
+        elements[0] = 1.0 - 2.0 * (y * y + z * z);
+        elements[1] = 2.0 * (x * y + z * w);
+        elements[2] = 2.0 * (x * z - y * w);
+        elements[4] = 2.0 * (x * y - z * w);
+        elements[5] = 1.0 - 2.0 * (x * x + z * z);
+        elements[6] = 2.0 * (y * z + x * w);
+        elements[8] = 2.0 * (x * z + y * w);
+        elements[9] = 2.0 * (y * z - x * w);
+        elements[10] = 1.0 - 2.0 * (x * x + y * y);
+        elements[15] = 1.0;
+        Matrix4 { elements }
+    }
+
+    /// Spherical linear interpolation. Falls back to a normalized lerp when
+    /// the quaternions are nearly parallel (where slerp's `sin(theta)`
+    /// divisor would blow up), and flips `b` to take the short path when
+    /// the inputs are more than 90 degrees apart.
+    pub fn slerp(a: Self, b: Self, t: f64) -> Self {
+        let mut dot = a.dot(b);
+        let mut b = b;
+        if dot < 0.0 {
+            b = Self {
+                x: -b.x,
+                y: -b.y,
+                z: -b.z,
+                w: -b.w,
+            };
+            dot = -dot;
+        }
+
+        if dot > 0.9995 {
+            return Self {
+                x: a.x + (b.x - a.x) * t,
+                y: a.y + (b.y - a.y) * t,
+                z: a.z + (b.z - a.z) * t,
+                w: a.w + (b.w - a.w) * t,
+            }
+            .normalize();
+        }
+
+        let theta_0 = dot.acos();
+        let theta = theta_0 * t;
+        let (s, c) = theta.sin_cos();
+        let sin_theta_0 = theta_0.sin();
+
+        let scale_b = s / sin_theta_0;
+        let scale_a = c - dot * scale_b;
+
+        Self {
+            x: a.x * scale_a + b.x * scale_b,
+            y: a.y * scale_a + b.y * scale_b,
+            z: a.z * scale_a + b.z * scale_b,
+            w: a.w * scale_a + b.w * scale_b,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_rotates_nothing() {
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(Quaternion::identity().rotate_vector(v), v);
+    }
+
+    #[test]
+    fn axis_angle_rotates_90_degrees() {
+        let q = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), std::f64::consts::FRAC_PI_2);
+        let rotated = q.rotate_vector(Vector3::new(1.0, 0.0, 0.0));
+        assert!((rotated.x).abs() < 1e-9);
+        assert!((rotated.y - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn to_matrix4_matches_rotate_vector() {
+        let q = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 0.7);
+        let v = Vector3::new(1.0, 0.0, 0.0);
+        let via_quaternion = q.rotate_vector(v);
+        let via_matrix = q.to_matrix4().transform_point(v);
+        assert!((via_quaternion.x - via_matrix.x).abs() < 1e-9);
+        assert!((via_quaternion.y - via_matrix.y).abs() < 1e-9);
+        assert!((via_quaternion.z - via_matrix.z).abs() < 1e-9);
+    }
+
+    #[test]
+    fn slerp_endpoints() {
+        let a = Quaternion::identity();
+        let b = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 1.0);
+        assert_eq!(Quaternion::slerp(a, b, 0.0), a);
+        let at_end = Quaternion::slerp(a, b, 1.0);
+        assert!((at_end.x - b.x).abs() < 1e-9);
+        assert!((at_end.w - b.w).abs() < 1e-9);
+    }
+
+    #[test]
+    fn slerp_takes_short_path() {
+        let a = Quaternion::identity();
+        let b = Quaternion {
+            x: -a.x,
+            y: -a.y,
+            z: -a.z,
+            w: -a.w,
+        };
+        // `b` is the antipodal representation of `a` (same rotation); slerp
+        // should still behave like interpolating towards `a` itself.
+        let mid = Quaternion::slerp(a, b, 0.5);
+        assert!((mid.dot(a).abs() - 1.0).abs() < 1e-9);
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 
-use crate::Vector3;
+use crate::{plane_split, Matrix4, Quaternion, Vector3};
 
 pub type EntityId = u64;
 pub type ComponentId = u64;
@@ -41,13 +41,126 @@ pub struct Aabb {
     pub max: Vector3,
 }
 
+impl Aabb {
+    /// An inverted box that any `union` absorbs, used as the starting
+    /// accumulator for reducing over a set of points or boxes.
+    pub fn empty() -> Self {
+        Self {
+            min: Vector3::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+            max: Vector3::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+        }
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            min: Vector3::new(
+                self.min.x.min(other.min.x),
+                self.min.y.min(other.min.y),
+                self.min.z.min(other.min.z),
+            ),
+            max: Vector3::new(
+                self.max.x.max(other.max.x),
+                self.max.y.max(other.max.y),
+                self.max.z.max(other.max.z),
+            ),
+        }
+    }
+
+    pub fn union_point(self, point: Vector3) -> Self {
+        self.union(Self {
+            min: point,
+            max: point,
+        })
+    }
+
+    pub fn contains(self, point: Vector3) -> bool {
+        point.x >= self.min.x
+            && point.x <= self.max.x
+            && point.y >= self.min.y
+            && point.y <= self.max.y
+            && point.z >= self.min.z
+            && point.z <= self.max.z
+    }
+
+    pub fn intersects(self, other: Self) -> bool {
+        self.min.x <= other.max.x
+            && self.max.x >= other.min.x
+            && self.min.y <= other.max.y
+            && self.max.y >= other.min.y
+            && self.min.z <= other.max.z
+            && self.max.z >= other.min.z
+    }
+
+    pub fn center(self) -> Vector3 {
+        self.min.add(self.max).scale(0.5)
+    }
+
+    pub fn extents(self) -> Vector3 {
+        self.max.sub(self.min).scale(0.5)
+    }
+
+    /// Re-derives a world-space box by transforming all eight corners and
+    /// re-accumulating min/max, since an AABB is not preserved under an
+    /// arbitrary affine transform.
+    pub fn transform(self, matrix: &Matrix4) -> Self {
+        let mut out = Self::empty();
+        for &x in &[self.min.x, self.max.x] {
+            for &y in &[self.min.y, self.max.y] {
+                for &z in &[self.min.z, self.max.z] {
+                    out = out.union_point(matrix.transform_point(Vector3::new(x, y, z)));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// A transform's rotation, carried either as Euler angles (legacy scenes,
+/// and still convenient for authoring) or as a `Quaternion` (gimbal-lock
+/// free, and required for `Track`/`AnimationClip` interpolation).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Rotation {
+    Euler(Vector3),
+    Quaternion(Quaternion),
+}
+
+impl Rotation {
+    pub fn to_quaternion(self) -> Quaternion {
+        match self {
+            Rotation::Euler(euler) => Quaternion::from_euler(euler),
+            Rotation::Quaternion(quaternion) => quaternion,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct TransformComponent {
     pub position: Vector3,
-    pub rotation: Vector3,
+    pub rotation: Rotation,
     pub scale: Vector3,
 }
 
+impl TransformComponent {
+    pub fn identity() -> Self {
+        Self {
+            position: Vector3::zero(),
+            rotation: Rotation::Euler(Vector3::zero()),
+            scale: Vector3::new(1.0, 1.0, 1.0),
+        }
+    }
+
+    /// Composes this transform into a world-space `Matrix4`, regardless of
+    /// whether its rotation is stored as Euler angles or a quaternion.
+    pub fn to_matrix4(self) -> Matrix4 {
+        match self.rotation {
+            Rotation::Euler(euler) => Matrix4::compose(self.position, euler, self.scale),
+            Rotation::Quaternion(quaternion) => {
+                Matrix4::compose_quat(self.position, quaternion, self.scale)
+            }
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum GeometryTopology {
     Triangles,
@@ -176,3 +289,407 @@ pub struct SceneGraph {
     pub components: ComponentTable,
     pub assets: AssetRegistry,
 }
+
+impl SceneGraph {
+    /// Computes the union of every entity's world-space bounds, or `None`
+    /// if no entity contributes a bound. Feeds "frame all" and culling.
+    pub fn world_bounds(&self) -> Option<Aabb> {
+        let mut bounds = Aabb::empty();
+        let mut found = false;
+
+        for entity in &self.entities {
+            let Some(geometry_id) = entity.components.geometry else {
+                continue;
+            };
+            let Some(geometry) = self.components.geometries.get(&geometry_id) else {
+                continue;
+            };
+            let local_bounds = geometry
+                .local_bounds
+                .or_else(|| self.assets.meshes.get(&geometry.mesh).and_then(|m| m.bounds));
+            let Some(local_bounds) = local_bounds else {
+                continue;
+            };
+
+            let transform = entity
+                .components
+                .transform
+                .and_then(|id| self.components.transforms.get(&id))
+                .copied()
+                .unwrap_or_else(TransformComponent::identity);
+
+            bounds = bounds.union(local_bounds.transform(&transform.to_matrix4()));
+            found = true;
+        }
+
+        found.then_some(bounds)
+    }
+
+    /// Orders every entity with opacity-bearing material back-to-front as
+    /// seen from `eye`, so naive distance sorting doesn't interleave
+    /// overlapping transparent geometry. Each entity's world-space `Aabb`
+    /// stands in for its true geometry, split into a BSP tree over its six
+    /// faces (see `plane_split`).
+    pub fn transparency_order(&self, eye: Vector3) -> Vec<EntityId> {
+        let mut polygons = Vec::new();
+
+        for entity in &self.entities {
+            let Some(material_id) = entity.components.material else {
+                continue;
+            };
+            let Some(material) = self.components.materials.get(&material_id) else {
+                continue;
+            };
+            if material.opacity >= 1.0 {
+                continue;
+            }
+
+            let Some(geometry_id) = entity.components.geometry else {
+                continue;
+            };
+            let Some(geometry) = self.components.geometries.get(&geometry_id) else {
+                continue;
+            };
+            let local_bounds = geometry
+                .local_bounds
+                .or_else(|| self.assets.meshes.get(&geometry.mesh).and_then(|m| m.bounds));
+            let Some(local_bounds) = local_bounds else {
+                continue;
+            };
+
+            let transform = entity
+                .components
+                .transform
+                .and_then(|id| self.components.transforms.get(&id))
+                .copied()
+                .unwrap_or_else(TransformComponent::identity);
+            let world_bounds = local_bounds.transform(&transform.to_matrix4());
+            polygons.extend(aabb_faces(world_bounds, entity.id));
+        }
+
+        plane_split::order_back_to_front(polygons, eye)
+    }
+}
+
+/// The six quads bounding `aabb`, wound consistently and tagged with
+/// `source` so a BSP split can still trace a fragment back to its entity.
+fn aabb_faces(aabb: Aabb, source: EntityId) -> Vec<plane_split::Polygon> {
+    let corners = [
+        Vector3::new(aabb.min.x, aabb.min.y, aabb.min.z),
+        Vector3::new(aabb.max.x, aabb.min.y, aabb.min.z),
+        Vector3::new(aabb.max.x, aabb.max.y, aabb.min.z),
+        Vector3::new(aabb.min.x, aabb.max.y, aabb.min.z),
+        Vector3::new(aabb.min.x, aabb.min.y, aabb.max.z),
+        Vector3::new(aabb.max.x, aabb.min.y, aabb.max.z),
+        Vector3::new(aabb.max.x, aabb.max.y, aabb.max.z),
+        Vector3::new(aabb.min.x, aabb.max.y, aabb.max.z),
+    ];
+    let faces: [[usize; 4]; 6] = [
+        [0, 3, 2, 1], // -z
+        [4, 5, 6, 7], // +z
+        [0, 1, 5, 4], // -y
+        [3, 7, 6, 2], // +y
+        [0, 4, 7, 3], // -x
+        [1, 2, 6, 5], // +x
+    ];
+
+    faces
+        .iter()
+        .map(|face| plane_split::Polygon {
+            vertices: face.iter().map(|&i| corners[i]).collect(),
+            source,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_box() -> Aabb {
+        Aabb {
+            min: Vector3::new(-1.0, -1.0, -1.0),
+            max: Vector3::new(1.0, 1.0, 1.0),
+        }
+    }
+
+    #[test]
+    fn aabb_union_grows_to_fit() {
+        let a = unit_box();
+        let b = Aabb {
+            min: Vector3::new(0.0, 0.0, 0.0),
+            max: Vector3::new(3.0, 0.5, 0.5),
+        };
+        let u = a.union(b);
+        assert_eq!(u.min, Vector3::new(-1.0, -1.0, -1.0));
+        assert_eq!(u.max, Vector3::new(3.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn aabb_empty_is_absorbed_by_union() {
+        let a = unit_box();
+        assert_eq!(Aabb::empty().union(a), a);
+    }
+
+    #[test]
+    fn aabb_contains_and_intersects() {
+        let a = unit_box();
+        assert!(a.contains(Vector3::zero()));
+        assert!(!a.contains(Vector3::new(2.0, 0.0, 0.0)));
+
+        let touching = Aabb {
+            min: Vector3::new(1.0, -1.0, -1.0),
+            max: Vector3::new(2.0, 1.0, 1.0),
+        };
+        assert!(a.intersects(touching));
+
+        let separate = Aabb {
+            min: Vector3::new(5.0, 5.0, 5.0),
+            max: Vector3::new(6.0, 6.0, 6.0),
+        };
+        assert!(!a.intersects(separate));
+    }
+
+    #[test]
+    fn aabb_center_and_extents() {
+        let a = unit_box();
+        assert_eq!(a.center(), Vector3::zero());
+        assert_eq!(a.extents(), Vector3::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn aabb_transform_translates_box() {
+        let a = unit_box();
+        let m = Matrix4::compose(
+            Vector3::new(5.0, 0.0, 0.0),
+            Vector3::zero(),
+            Vector3::new(1.0, 1.0, 1.0),
+        );
+        let transformed = a.transform(&m);
+        assert_eq!(transformed.min, Vector3::new(4.0, -1.0, -1.0));
+        assert_eq!(transformed.max, Vector3::new(6.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn world_bounds_unions_entity_geometry() {
+        let mesh_id: AssetId = 1;
+        let mut meshes = BTreeMap::new();
+        meshes.insert(
+            mesh_id,
+            MeshAsset {
+                id: mesh_id,
+                name: None,
+                vertex_count: 8,
+                index_count: 36,
+                index_format: IndexFormat::Uint16,
+                topology: GeometryTopology::Triangles,
+                layout: BufferLayout {
+                    position: BufferLayoutEntry {
+                        offset: 0,
+                        stride: 12,
+                    },
+                    normal: None,
+                    uv: None,
+                },
+                source_uri: None,
+                bounds: Some(unit_box()),
+            },
+        );
+
+        let transform_id: ComponentId = 1;
+        let mut transforms = BTreeMap::new();
+        transforms.insert(
+            transform_id,
+            TransformComponent {
+                position: Vector3::new(10.0, 0.0, 0.0),
+                rotation: Rotation::Euler(Vector3::zero()),
+                scale: Vector3::new(1.0, 1.0, 1.0),
+            },
+        );
+
+        let geometry_id: ComponentId = 1;
+        let mut geometries = BTreeMap::new();
+        geometries.insert(
+            geometry_id,
+            GeometryComponent {
+                mesh: mesh_id,
+                topology: GeometryTopology::Triangles,
+                local_bounds: None,
+            },
+        );
+
+        let scene = SceneGraph {
+            schema_version: SchemaVersion::V0,
+            metadata: SceneMetadata {
+                name: "test".to_string(),
+                description: None,
+                unit: Unit::M,
+                up_axis: Axis::Y,
+                created_at: "0".to_string(),
+                updated_at: "0".to_string(),
+            },
+            entities: vec![EntityRecord {
+                id: 1,
+                name: None,
+                components: ComponentRefs {
+                    transform: Some(transform_id),
+                    geometry: Some(geometry_id),
+                    material: None,
+                    layer: None,
+                    metadata: None,
+                },
+            }],
+            components: ComponentTable {
+                transforms,
+                geometries,
+                materials: BTreeMap::new(),
+                layers: BTreeMap::new(),
+                metadata: BTreeMap::new(),
+            },
+            assets: AssetRegistry {
+                meshes,
+                materials: BTreeMap::new(),
+                textures: BTreeMap::new(),
+            },
+        };
+
+        let bounds = scene.world_bounds().unwrap();
+        assert_eq!(bounds.min, Vector3::new(9.0, -1.0, -1.0));
+        assert_eq!(bounds.max, Vector3::new(11.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn world_bounds_none_when_no_geometry() {
+        let scene = SceneGraph {
+            schema_version: SchemaVersion::V0,
+            metadata: SceneMetadata {
+                name: "empty".to_string(),
+                description: None,
+                unit: Unit::M,
+                up_axis: Axis::Y,
+                created_at: "0".to_string(),
+                updated_at: "0".to_string(),
+            },
+            entities: vec![],
+            components: ComponentTable {
+                transforms: BTreeMap::new(),
+                geometries: BTreeMap::new(),
+                materials: BTreeMap::new(),
+                layers: BTreeMap::new(),
+                metadata: BTreeMap::new(),
+            },
+            assets: AssetRegistry {
+                meshes: BTreeMap::new(),
+                materials: BTreeMap::new(),
+                textures: BTreeMap::new(),
+            },
+        };
+
+        assert!(scene.world_bounds().is_none());
+    }
+
+    #[test]
+    fn transform_component_supports_quaternion_rotation() {
+        let quaternion = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), std::f64::consts::FRAC_PI_2);
+        let transform = TransformComponent {
+            position: Vector3::zero(),
+            rotation: Rotation::Quaternion(quaternion),
+            scale: Vector3::new(1.0, 1.0, 1.0),
+        };
+        let rotated = transform.to_matrix4().transform_point(Vector3::new(1.0, 0.0, 0.0));
+        assert!(rotated.x.abs() < 1e-9);
+        assert!((rotated.y - 1.0).abs() < 1e-9);
+    }
+
+    fn box_entity(id: EntityId, z: f64, opacity: f32) -> (EntityRecord, TransformComponent, GeometryComponent, MaterialComponent) {
+        let entity = EntityRecord {
+            id,
+            name: None,
+            components: ComponentRefs {
+                transform: Some(id),
+                geometry: Some(id),
+                material: Some(id),
+                layer: None,
+                metadata: None,
+            },
+        };
+        let transform = TransformComponent {
+            position: Vector3::new(0.0, 0.0, z),
+            rotation: Rotation::Euler(Vector3::zero()),
+            scale: Vector3::new(1.0, 1.0, 1.0),
+        };
+        let geometry = GeometryComponent {
+            mesh: id,
+            topology: GeometryTopology::Triangles,
+            local_bounds: Some(unit_box()),
+        };
+        let material = MaterialComponent {
+            base_color: [1.0, 1.0, 1.0, 1.0],
+            metallic: 0.0,
+            roughness: 1.0,
+            opacity,
+        };
+        (entity, transform, geometry, material)
+    }
+
+    fn scene_with(boxes: Vec<(EntityId, f64, f32)>) -> SceneGraph {
+        let mut entities = Vec::new();
+        let mut transforms = BTreeMap::new();
+        let mut geometries = BTreeMap::new();
+        let mut materials = BTreeMap::new();
+
+        for (id, z, opacity) in boxes {
+            let (entity, transform, geometry, material) = box_entity(id, z, opacity);
+            entities.push(entity);
+            transforms.insert(id, transform);
+            geometries.insert(id, geometry);
+            materials.insert(id, material);
+        }
+
+        SceneGraph {
+            schema_version: SchemaVersion::V0,
+            metadata: SceneMetadata {
+                name: "transparency".to_string(),
+                description: None,
+                unit: Unit::M,
+                up_axis: Axis::Y,
+                created_at: "0".to_string(),
+                updated_at: "0".to_string(),
+            },
+            entities,
+            components: ComponentTable {
+                transforms,
+                geometries,
+                materials,
+                layers: BTreeMap::new(),
+                metadata: BTreeMap::new(),
+            },
+            assets: AssetRegistry {
+                meshes: BTreeMap::new(),
+                materials: BTreeMap::new(),
+                textures: BTreeMap::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn transparency_order_sorts_back_to_front_from_eye() {
+        let scene = scene_with(vec![(1, 5.0, 0.5), (2, -5.0, 0.5)]);
+        let order = scene.transparency_order(Vector3::new(0.0, 0.0, 10.0));
+        assert_eq!(order, vec![2, 1]);
+    }
+
+    #[test]
+    fn transparency_order_flips_when_eye_crosses_over() {
+        let scene = scene_with(vec![(1, 5.0, 0.5), (2, -5.0, 0.5)]);
+        let order = scene.transparency_order(Vector3::new(0.0, 0.0, -10.0));
+        assert_eq!(order, vec![1, 2]);
+    }
+
+    #[test]
+    fn transparency_order_skips_opaque_entities() {
+        let scene = scene_with(vec![(1, 5.0, 1.0), (2, -5.0, 0.5)]);
+        let order = scene.transparency_order(Vector3::new(0.0, 0.0, 10.0));
+        assert_eq!(order, vec![2]);
+    }
+}
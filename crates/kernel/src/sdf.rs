@@ -0,0 +1,314 @@
+use crate::{
+    Aabb, AssetId, BufferLayout, BufferLayoutEntry, GeometryTopology, IndexFormat, Matrix4,
+    Mesh, MeshAsset, Vector3,
+};
+
+/// A procedural signed-distance field: negative inside the surface,
+/// positive outside, zero at the boundary.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Sdf {
+    Sphere { radius: f64 },
+    Box { half_extents: Vector3 },
+    Cylinder { radius: f64, height: f64 },
+    Torus { major_radius: f64, minor_radius: f64 },
+    Plane { normal: Vector3, distance: f64 },
+    Union(Box<Sdf>, Box<Sdf>),
+    Intersection(Box<Sdf>, Box<Sdf>),
+    Difference(Box<Sdf>, Box<Sdf>),
+    SmoothUnion(Box<Sdf>, Box<Sdf>, f64),
+    Transform(Box<Sdf>, Matrix4),
+}
+
+impl Sdf {
+    pub fn eval(&self, p: Vector3) -> f64 {
+        match self {
+            Sdf::Sphere { radius } => p.length() - radius,
+            Sdf::Box { half_extents } => {
+                let q = Vector3::new(
+                    p.x.abs() - half_extents.x,
+                    p.y.abs() - half_extents.y,
+                    p.z.abs() - half_extents.z,
+                );
+                let outside = Vector3::new(q.x.max(0.0), q.y.max(0.0), q.z.max(0.0)).length();
+                let inside = q.x.max(q.y.max(q.z)).min(0.0);
+                outside + inside
+            }
+            Sdf::Cylinder { radius, height } => {
+                let d_radial = (p.x * p.x + p.z * p.z).sqrt() - radius;
+                let d_height = p.y.abs() - height * 0.5;
+                let outside =
+                    (d_radial.max(0.0).powi(2) + d_height.max(0.0).powi(2)).sqrt();
+                outside + d_radial.max(d_height).min(0.0)
+            }
+            Sdf::Torus {
+                major_radius,
+                minor_radius,
+            } => {
+                let q_x = (p.x * p.x + p.z * p.z).sqrt() - major_radius;
+                (q_x * q_x + p.y * p.y).sqrt() - minor_radius
+            }
+            Sdf::Plane { normal, distance } => normal.normalize().dot(p) - distance,
+            Sdf::Union(a, b) => a.eval(p).min(b.eval(p)),
+            Sdf::Intersection(a, b) => a.eval(p).max(b.eval(p)),
+            Sdf::Difference(a, b) => a.eval(p).max(-b.eval(p)),
+            Sdf::SmoothUnion(a, b, k) => {
+                let da = a.eval(p);
+                let db = b.eval(p);
+                let h = (0.5 + 0.5 * (db - da) / k).clamp(0.0, 1.0);
+                lerp(db, da, h) - k * h * (1.0 - h)
+            }
+            Sdf::Transform(child, matrix) => match matrix.invert() {
+                Some(inverse) => child.eval(inverse.transform_point(p)),
+                // A degenerate (non-invertible) transform collapses its
+                // geometry to zero volume; treat every point as infinitely
+                // far outside rather than panicking on otherwise-valid input.
+                None => f64::INFINITY,
+            },
+        }
+    }
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+const CORNER_OFFSETS: [(usize, usize, usize); 8] = [
+    (0, 0, 0),
+    (1, 0, 0),
+    (1, 1, 0),
+    (0, 1, 0),
+    (0, 0, 1),
+    (1, 0, 1),
+    (1, 1, 1),
+    (0, 1, 1),
+];
+
+const EDGE_VERTICES: [(usize, usize); 12] = [
+    (0, 1),
+    (1, 2),
+    (2, 3),
+    (3, 0),
+    (4, 5),
+    (5, 6),
+    (6, 7),
+    (7, 4),
+    (0, 4),
+    (1, 5),
+    (2, 6),
+    (3, 7),
+];
+
+/// Polygonizes the field over `bounds` at `resolution` cells (so
+/// `resolution + 1` samples) per axis using marching cubes: each cell's 8 corners are classified by sign into
+/// a case index, the edge table identifies which cell edges the surface
+/// crosses, and vertex positions are linearly interpolated along those
+/// edges where the field crosses zero.
+pub fn polygonize(sdf: &Sdf, bounds: Aabb, resolution: usize) -> Mesh {
+    let resolution = resolution.max(1);
+    let extents = bounds.max.sub(bounds.min);
+    let step = Vector3::new(
+        extents.x / resolution as f64,
+        extents.y / resolution as f64,
+        extents.z / resolution as f64,
+    );
+
+    let sample_point = |i: usize, j: usize, k: usize| -> Vector3 {
+        Vector3::new(
+            bounds.min.x + step.x * i as f64,
+            bounds.min.y + step.y * j as f64,
+            bounds.min.z + step.z * k as f64,
+        )
+    };
+
+    let mut positions = Vec::new();
+    let mut indices = Vec::new();
+
+    for i in 0..resolution {
+        for j in 0..resolution {
+            for k in 0..resolution {
+                let corners: Vec<(Vector3, f64)> = CORNER_OFFSETS
+                    .iter()
+                    .map(|(di, dj, dk)| {
+                        let point = sample_point(i + di, j + dj, k + dk);
+                        let value = sdf.eval(point);
+                        (point, value)
+                    })
+                    .collect();
+
+                let mut case_index = 0usize;
+                for (bit, (_, value)) in corners.iter().enumerate() {
+                    if *value < 0.0 {
+                        case_index |= 1 << bit;
+                    }
+                }
+
+                let edge_mask = EDGE_TABLE[case_index];
+                if edge_mask == 0 {
+                    continue;
+                }
+
+                let mut edge_points = [Vector3::zero(); 12];
+                for (edge, &(a, b)) in EDGE_VERTICES.iter().enumerate() {
+                    if edge_mask & (1 << edge) == 0 {
+                        continue;
+                    }
+                    let (pa, va) = corners[a];
+                    let (pb, vb) = corners[b];
+                    edge_points[edge] = interpolate_edge(pa, va, pb, vb);
+                }
+
+                for triangle in TRI_TABLE[case_index].chunks(3) {
+                    if triangle.len() < 3 || triangle[0] < 0 {
+                        break;
+                    }
+                    let base = positions.len() as u32 / 3;
+                    for &edge in triangle {
+                        let p = edge_points[edge as usize];
+                        positions.push(p.x as f32);
+                        positions.push(p.y as f32);
+                        positions.push(p.z as f32);
+                    }
+                    indices.push(base);
+                    indices.push(base + 1);
+                    indices.push(base + 2);
+                }
+            }
+        }
+    }
+
+    Mesh { positions, indices }
+}
+
+fn interpolate_edge(pa: Vector3, va: f64, pb: Vector3, vb: f64) -> Vector3 {
+    if (va - vb).abs() < 1e-9 {
+        return pa;
+    }
+    let t = -va / (vb - va);
+    pa.add(pb.sub(pa).scale(t))
+}
+
+/// Polygonizes `sdf` and wraps the result as a `MeshAsset` so procedurally
+/// generated parts can be registered in an `AssetRegistry` like any
+/// imported mesh. The caller is responsible for keeping the returned
+/// `Mesh`'s raw vertex/index data alongside the asset, the same way any
+/// other `MeshAsset` needs its backing buffers stored separately.
+pub fn generate_mesh_asset(
+    sdf: &Sdf,
+    bounds: Aabb,
+    resolution: usize,
+    id: AssetId,
+    name: Option<String>,
+) -> (Mesh, MeshAsset) {
+    let mesh = polygonize(sdf, bounds, resolution);
+    let mesh_bounds = mesh
+        .positions
+        .chunks_exact(3)
+        .fold(Aabb::empty(), |acc, p| {
+            acc.union_point(Vector3::new(p[0] as f64, p[1] as f64, p[2] as f64))
+        });
+
+    let asset = MeshAsset {
+        id,
+        name,
+        vertex_count: (mesh.positions.len() / 3) as u32,
+        index_count: mesh.indices.len() as u32,
+        index_format: IndexFormat::Uint32,
+        topology: GeometryTopology::Triangles,
+        layout: BufferLayout {
+            position: BufferLayoutEntry {
+                offset: 0,
+                stride: 12,
+            },
+            normal: None,
+            uv: None,
+        },
+        source_uri: None,
+        bounds: Some(mesh_bounds),
+    };
+
+    (mesh, asset)
+}
+
+include!("sdf_tables.rs");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sphere_eval_signs() {
+        let sphere = Sdf::Sphere { radius: 1.0 };
+        assert!(sphere.eval(Vector3::zero()) < 0.0);
+        assert!((sphere.eval(Vector3::new(1.0, 0.0, 0.0))).abs() < 1e-9);
+        assert!(sphere.eval(Vector3::new(2.0, 0.0, 0.0)) > 0.0);
+    }
+
+    #[test]
+    fn union_takes_minimum() {
+        let a = Sdf::Sphere { radius: 1.0 };
+        let b = Sdf::Transform(
+            Box::new(Sdf::Sphere { radius: 1.0 }),
+            Matrix4::compose(Vector3::new(5.0, 0.0, 0.0), Vector3::zero(), Vector3::new(1.0, 1.0, 1.0)),
+        );
+        let union = Sdf::Union(Box::new(a.clone()), Box::new(b));
+        assert_eq!(union.eval(Vector3::zero()), a.eval(Vector3::zero()));
+    }
+
+    #[test]
+    fn transform_with_non_invertible_matrix_reports_outside_instead_of_panicking() {
+        let degenerate = Sdf::Transform(
+            Box::new(Sdf::Sphere { radius: 1.0 }),
+            Matrix4::compose(Vector3::zero(), Vector3::zero(), Vector3::zero()),
+        );
+        assert_eq!(degenerate.eval(Vector3::zero()), f64::INFINITY);
+    }
+
+    #[test]
+    fn difference_removes_b_from_a() {
+        let a = Sdf::Sphere { radius: 2.0 };
+        let b = Sdf::Sphere { radius: 1.0 };
+        let diff = Sdf::Difference(Box::new(a), Box::new(b));
+        assert!(diff.eval(Vector3::new(0.5, 0.0, 0.0)) > 0.0);
+        assert!(diff.eval(Vector3::new(1.5, 0.0, 0.0)) < 0.0);
+    }
+
+    #[test]
+    fn polygonize_sphere_produces_triangles() {
+        let sphere = Sdf::Sphere { radius: 1.0 };
+        let bounds = Aabb {
+            min: Vector3::new(-1.5, -1.5, -1.5),
+            max: Vector3::new(1.5, 1.5, 1.5),
+        };
+        let mesh = polygonize(&sphere, bounds, 8);
+        assert!(!mesh.indices.is_empty());
+        assert_eq!(mesh.indices.len() % 3, 0);
+        assert_eq!(mesh.positions.len() % 3, 0);
+    }
+
+    #[test]
+    fn generate_mesh_asset_matches_polygonized_counts() {
+        let sphere = Sdf::Sphere { radius: 1.0 };
+        let bounds = Aabb {
+            min: Vector3::new(-1.5, -1.5, -1.5),
+            max: Vector3::new(1.5, 1.5, 1.5),
+        };
+        let (mesh, asset) = generate_mesh_asset(&sphere, bounds, 8, 1, Some("sphere".to_string()));
+        assert_eq!(asset.vertex_count as usize, mesh.positions.len() / 3);
+        assert_eq!(asset.index_count as usize, mesh.indices.len());
+        assert!(asset.bounds.is_some());
+    }
+
+    #[test]
+    fn polygonize_empty_field_has_no_triangles() {
+        let plane = Sdf::Plane {
+            normal: Vector3::new(0.0, 1.0, 0.0),
+            distance: 100.0,
+        };
+        let bounds = Aabb {
+            min: Vector3::new(-1.0, -1.0, -1.0),
+            max: Vector3::new(1.0, 1.0, 1.0),
+        };
+        let mesh = polygonize(&plane, bounds, 4);
+        assert!(mesh.indices.is_empty());
+    }
+}